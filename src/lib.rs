@@ -1,3 +1,8 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use blake2::{Blake2b512, Digest};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::js_sys::{Array, Function, Object, Promise, Reflect};
@@ -19,20 +24,94 @@ macro_rules! get {
 
 const NULL: JsValue = JsValue::null();
 
+struct State {
+    accounts: Vec<Account>,
+    selected: Option<u8>,
+}
+
+/// A closure registered with the extension's `accounts.subscribe`, kept
+/// alive alongside the `unsubscribe` function it returned so dropping the
+/// subscription (or the extension) tears it down instead of leaking it.
+struct Subscription {
+    unsubscribe: Function,
+    _closure: Closure<dyn FnMut(JsValue)>,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        let _ = self.unsubscribe.call0(&NULL);
+    }
+}
+
 #[wasm_bindgen]
 pub struct PjsExtension {
     pjs: JsValue,
-    accounts: Vec<Account>,
-    selected: Option<u8>,
+    state: Rc<RefCell<State>>,
+    subscription: Option<Subscription>,
+}
+
+fn accounts_from(accounts: &JsValue) -> Vec<Account> {
+    Array::from(accounts)
+        .iter()
+        .map(|a| {
+            let name = get!(&a, "name").as_string().unwrap_or_default();
+            let address = get!(&a, "address").as_string().unwrap_or_default();
+            let net: NetworkInfo = get!(&a, "genesisHash").into();
+            Account { name, address, net }
+        })
+        .collect()
+}
+
+/// Clamps a previously selected index into `len`, or `None` once the list
+/// is empty.
+fn clamp_selected(selected: Option<u8>, len: usize) -> Option<u8> {
+    let last = len.checked_sub(1)?;
+    Some(selected.unwrap_or(0).min(last.min(u8::MAX as usize) as u8))
 }
 
 #[wasm_bindgen]
 impl PjsExtension {
+    /// Connects to `polkadot-js` when present, otherwise the first provider
+    /// reported by [`PjsExtension::providers`].
     pub async fn connect(app_name: &str) -> Result<PjsExtension, Error> {
-        let Some(web3) = web_sys::window().expect("browser").get("injectedWeb3") else {
-            return Err(Error::ExtensionUnavailable);
-        };
-        let pjs = get!(web3, "polkadot-js");
+        let providers = Self::providers()?;
+        let provider_id = providers
+            .iter()
+            .find(|p| p.id == "polkadot-js")
+            .or_else(|| providers.first())
+            .ok_or(Error::NoProviders)?
+            .id
+            .clone();
+        Self::connect_with(provider_id.as_str(), app_name).await
+    }
+
+    /// Lists every provider key under `window.injectedWeb3`, e.g.
+    /// `polkadot-js`, `talisman`, `subwallet-js`, `nova`, alongside the
+    /// version each reports.
+    pub fn providers() -> Result<Vec<ProviderInfo>, Error> {
+        let web3 = Self::injected_web3()?;
+        let keys = Object::keys(web3.unchecked_ref());
+        if keys.length() == 0 {
+            return Err(Error::NoProviders);
+        }
+        Ok(keys
+            .iter()
+            .map(|k| {
+                let id = k.as_string().unwrap_or_default();
+                let version = get!(&web3, id.as_str(), "version")
+                    .as_string()
+                    .unwrap_or_default();
+                ProviderInfo { id, version }
+            })
+            .collect())
+    }
+
+    /// Connects to a specific provider from [`PjsExtension::providers`]
+    /// rather than assuming `polkadot-js`.
+    #[wasm_bindgen(js_name = connectWith)]
+    pub async fn connect_with(provider_id: &str, app_name: &str) -> Result<PjsExtension, Error> {
+        let web3 = Self::injected_web3()?;
+        let pjs = get!(web3, provider_id);
         let enable: Function = get!(^ &pjs, "enable");
         let p = enable
             .call1(&pjs, &app_name.into())
@@ -44,36 +123,87 @@ impl PjsExtension {
 
         Ok(Self {
             pjs,
-            accounts: vec![],
-            selected: None,
+            state: Rc::new(RefCell::new(State {
+                accounts: vec![],
+                selected: None,
+            })),
+            subscription: None,
         })
     }
 
+    fn injected_web3() -> Result<JsValue, Error> {
+        web_sys::window()
+            .expect("browser")
+            .get("injectedWeb3")
+            .ok_or(Error::ExtensionUnavailable)
+    }
+
     #[wasm_bindgen(js_name = account)]
     pub fn current_account(&self) -> Result<Account, Error> {
-        let account = self.accounts
-            .get(self.selected.ok_or(Error::NoAccountSelected)? as usize)
+        let state = self.state.borrow();
+        let account = state
+            .accounts
+            .get(state.selected.ok_or(Error::NoAccountSelected)? as usize)
             .ok_or(Error::NoAccounts)?;
         Ok(account.clone())
     }
 
     #[wasm_bindgen(js_name = selectAccount)]
     pub fn select_account(&mut self, idx: u8) {
-        self.selected = self
-            .accounts
-            .len()
-            .checked_sub(1)
-            .map(|i| idx.min(i.min(u8::MAX as usize) as u8));
+        let mut state = self.state.borrow_mut();
+        state.selected = clamp_selected(Some(idx), state.accounts.len());
     }
 
-    ///
+    /// Subscribes to `accounts.subscribe`, rebuilding the cached account
+    /// list and forwarding an `AccountsChanged` event to `cb` whenever the
+    /// user adds, removes, or renames an account in the extension.
+    #[wasm_bindgen(js_name = subscribeAccounts)]
+    pub fn subscribe_accounts(&mut self, cb: &Function) -> Result<(), Error> {
+        let subscribe: Function = get!(^ &self.pjs, "accounts", "subscribe");
+        let state = Rc::clone(&self.state);
+        let cb = cb.clone();
+        let closure = Closure::wrap(Box::new(move |accounts: JsValue| {
+            let accounts = accounts_from(&accounts);
+            let mut state = state.borrow_mut();
+            state.selected = clamp_selected(state.selected, accounts.len());
+            state.accounts = accounts.clone();
+            drop(state);
+
+            let list = Array::new();
+            for account in accounts {
+                list.push(&JsValue::from(account));
+            }
+            let event = Object::new();
+            Reflect::set(&event, &"type".into(), &"AccountsChanged".into()).unwrap();
+            Reflect::set(&event, &"accounts".into(), &list).unwrap();
+            let _ = cb.call1(&NULL, &event);
+        }) as Box<dyn FnMut(JsValue)>);
+
+        let unsubscribe = subscribe
+            .call1(&NULL, closure.as_ref().unchecked_ref())
+            .map_err(|_| Error::FailedSubscribing)?
+            .unchecked_into::<Function>();
+
+        self.subscription = Some(Subscription {
+            unsubscribe,
+            _closure: closure,
+        });
+        Ok(())
+    }
+
+    /// Tears down a subscription registered with `subscribeAccounts`, if any.
+    pub fn unsubscribe(&mut self) {
+        self.subscription = None;
+    }
+
+    /// Calls `signer.signRaw` and hands the resolved result to `cb`, whose
+    /// return value is passed straight back to the caller — `cb` extracts
+    /// whatever it needs from the result itself, so no `signature` field is
+    /// assumed on its return value.
     #[wasm_bindgen(js_name = sign)]
     pub async fn js_sign(&self, payload: &str, cb: &Function) -> Result<JsValue, Error> {
         let sign: Function = get!(^ &self.pjs, "signer", "signRaw");
-        let account = self
-            .accounts
-            .get(self.selected.ok_or(Error::NoAccountSelected)? as usize)
-            .ok_or(Error::NoAccounts)?;
+        let account = self.current_account()?;
         let data = {
             let o = Object::new();
             Reflect::set(&o, &"address".into(), &account.address.as_str().into()).unwrap();
@@ -87,8 +217,20 @@ impl PjsExtension {
             .expect("promise")
             .unchecked_into::<Promise>();
         let signature = JsFuture::from(p).await.map_err(|_| Error::Sign)?;
-        let res = cb.call1(&NULL, &signature).map_err(|_| Error::Sign)?;
-        Ok(get!(&res, "signature"))
+        cb.call1(&NULL, &signature).map_err(|_| Error::Sign)
+    }
+
+    /// Same `cb` contract as [`PjsExtension::js_sign`], but calls
+    /// `signer.signPayload` for a submittable extrinsic payload instead.
+    #[wasm_bindgen(js_name = signPayload)]
+    pub async fn js_sign_payload(&self, payload: JsValue, cb: &Function) -> Result<JsValue, Error> {
+        let sign: Function = get!(^ &self.pjs, "signer", "signPayload");
+        let p = sign
+            .call1(&NULL, &payload)
+            .expect("promise")
+            .unchecked_into::<Promise>();
+        let signature = JsFuture::from(p).await.map_err(|_| Error::Sign)?;
+        cb.call1(&NULL, &signature).map_err(|_| Error::Sign)
     }
 
     ///
@@ -99,63 +241,70 @@ impl PjsExtension {
         let Ok(accounts) = JsFuture::from(p).await else {
             return Err(Error::FailedFetchingAccounts);
         };
-        self.accounts = Array::from(&accounts)
-            .iter()
-            .map(|a| {
-                let name = get!(&a, "name").as_string().unwrap();
-                let address = get!(&a, "address").as_string().unwrap();
-                let net: Network = get!(&a, "genesisHash").into();
-                Account { name, address, net }
-            })
-            .collect();
-        if !self.accounts.is_empty() {
-            self.selected = Some(0);
-        }
+        let accounts = accounts_from(&accounts);
+        let mut state = self.state.borrow_mut();
+        state.selected = if accounts.is_empty() { None } else { Some(0) };
+        state.accounts = accounts;
         Ok(())
     }
 
     #[wasm_bindgen(getter)]
     pub fn accounts(&self) -> Vec<Account> {
-        self.accounts.clone()
+        self.state.borrow().accounts.clone()
     }
 
     #[wasm_bindgen(getter, js_name = selectedAccount)]
     pub fn get_selected(&self) -> Option<Account> {
-        self.selected
-            .and_then(|a| self.accounts.get(a as usize))
-            .cloned()
+        let state = self.state.borrow();
+        state.selected.and_then(|a| state.accounts.get(a as usize)).cloned()
     }
 }
 
 impl PjsExtension {
-    pub async fn sign(&self, payload: &[u8]) -> Result<[u8; 64], Error> {
+    pub async fn sign(&self, payload: &[u8]) -> Result<MultiSignature, Error> {
         let payload = Self::to_hex(payload);
-        let mut signature = [0u8; 64];
-        let cb = Closure::wrap(Box::new(move |s: JsValue| {
-            Self::from_hex(s.as_string().unwrap_or_default().as_str(), &mut signature)
-        }) as Box<dyn FnMut(JsValue)>);
+        let (result, cb) = Self::capture_signature();
         self.js_sign(payload.as_str(), cb.as_ref().unchecked_ref())
             .await?;
-        Ok(signature)
+        let hex = result.borrow_mut().take().ok_or(Error::Sign)?;
+        MultiSignature::from_raw_hex(hex.as_str())
+    }
+
+    /// Signs a submittable extrinsic rather than a raw blob, via the
+    /// extension's `signer.signPayload`. Fills in `address` from the
+    /// selected account when the caller leaves it blank.
+    pub async fn sign_payload(&self, mut payload: SignerPayload) -> Result<MultiSignature, Error> {
+        if payload.address.is_empty() {
+            payload.address = self.current_account()?.address;
+        }
+        let (result, cb) = Self::capture_signature();
+        self.js_sign_payload(payload.to_object().into(), cb.as_ref().unchecked_ref())
+            .await?;
+        let hex = result.borrow_mut().take().ok_or(Error::Sign)?;
+        MultiSignature::from_hex(hex.as_str())
+    }
+
+    /// Builds the `cb` half of the `js_sign`/`js_sign_payload` contract: a
+    /// closure that stashes the resolved `signature` field where the caller
+    /// can read it back out once the async call completes.
+    fn capture_signature() -> (Rc<RefCell<Option<String>>>, Closure<dyn FnMut(JsValue)>) {
+        let result = Rc::new(RefCell::new(None));
+        let slot = Rc::clone(&result);
+        let cb = Closure::wrap(Box::new(move |s: JsValue| {
+            *slot.borrow_mut() = get!(&s, "signature").as_string();
+        }) as Box<dyn FnMut(JsValue)>);
+        (result, cb)
     }
 
     fn to_hex(bytes: &[u8]) -> String {
         use std::fmt::Write;
-        let mut s = String::with_capacity(2 + bytes.len());
+        let mut s = String::with_capacity(2 + 2 * bytes.len());
         let _ = write!(s, "0x");
         for b in bytes {
-            let _ = write!(s, "{b:x}");
+            let _ = write!(s, "{b:02x}");
         }
         s
     }
-    fn from_hex(input: &str, buf: &mut [u8]) {
-        for (i, b) in buf.iter_mut().enumerate() {
-            let Some(s) = input.get(i * 2..i * 2 + 2) else {
-                return;
-            };
-            *b = u8::from_str_radix(s, 16).unwrap_or_default();
-        }
-    }
 }
 
 #[wasm_bindgen]
@@ -167,6 +316,216 @@ pub enum Error {
     NoAccountSelected,
     NoAccounts,
     Sign,
+    InvalidSignature,
+    NoProviders,
+    InvalidAddress,
+    FailedSubscribing,
+}
+
+/// A provider key found under `window.injectedWeb3`, e.g. `polkadot-js` or
+/// `talisman`, alongside the version it self-reports.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct ProviderInfo {
+    id: String,
+    version: String,
+}
+
+#[wasm_bindgen]
+impl ProviderInfo {
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> String {
+        self.id.clone()
+    }
+    #[wasm_bindgen(getter)]
+    pub fn version(&self) -> String {
+        self.version.clone()
+    }
+}
+
+/// The signature scheme an account uses, identified by the leading byte the
+/// extension prepends to a `MultiSignature` (`0x00` ed25519, `0x01` sr25519,
+/// `0x02` ecdsa).
+#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CryptoScheme {
+    Ed25519 = 0,
+    Sr25519 = 1,
+    Ecdsa = 2,
+}
+
+impl CryptoScheme {
+    fn signature_len(self) -> usize {
+        match self {
+            CryptoScheme::Ed25519 | CryptoScheme::Sr25519 => 64,
+            CryptoScheme::Ecdsa => 65,
+        }
+    }
+}
+
+impl TryFrom<u8> for CryptoScheme {
+    type Error = Error;
+
+    fn try_from(byte: u8) -> Result<Self, Error> {
+        match byte {
+            0x00 => Ok(CryptoScheme::Ed25519),
+            0x01 => Ok(CryptoScheme::Sr25519),
+            0x02 => Ok(CryptoScheme::Ecdsa),
+            _ => Err(Error::InvalidSignature),
+        }
+    }
+}
+
+/// A scheme-tagged signature, mirroring Substrate's `MultiSignature`: the
+/// leading variant byte plus the 64 (ed25519/sr25519) or 65 (ecdsa) byte
+/// signature that follows it.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct MultiSignature {
+    scheme: CryptoScheme,
+    bytes: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl MultiSignature {
+    #[wasm_bindgen(getter)]
+    pub fn scheme(&self) -> CryptoScheme {
+        self.scheme
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn bytes(&self) -> Vec<u8> {
+        self.bytes.clone()
+    }
+
+    #[wasm_bindgen(js_name = toHex)]
+    pub fn to_hex(&self) -> String {
+        let mut full = Vec::with_capacity(1 + self.bytes.len());
+        full.push(self.scheme as u8);
+        full.extend_from_slice(&self.bytes);
+        PjsExtension::to_hex(&full)
+    }
+}
+
+impl MultiSignature {
+    /// Decodes a SCALE-style `MultiSignature`: a leading scheme byte followed
+    /// by the 64/65-byte signature, as returned by `signer.signPayload`.
+    fn from_hex(input: &str) -> Result<Self, Error> {
+        let bytes = decode_hex(input)?;
+        let (scheme_byte, signature) = bytes.split_first().ok_or(Error::InvalidSignature)?;
+        let scheme = CryptoScheme::try_from(*scheme_byte)?;
+        if signature.len() != scheme.signature_len() {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(Self {
+            scheme,
+            bytes: signature.to_vec(),
+        })
+    }
+
+    /// Decodes a bare signature as returned by `signer.signRaw`, which omits
+    /// the leading scheme byte that the SCALE-encoded `MultiSignature` used
+    /// by `signPayload` carries. ed25519 and sr25519 both produce 64-byte
+    /// signatures and can't be told apart by length alone, so 64 bytes is
+    /// assumed sr25519 — the default scheme for injected substrate accounts;
+    /// 65 bytes is unambiguously ecdsa.
+    fn from_raw_hex(input: &str) -> Result<Self, Error> {
+        let bytes = decode_hex(input)?;
+        let scheme = match bytes.len() {
+            64 => CryptoScheme::Sr25519,
+            65 => CryptoScheme::Ecdsa,
+            _ => return Err(Error::InvalidSignature),
+        };
+        Ok(Self { scheme, bytes })
+    }
+}
+
+fn decode_hex(input: &str) -> Result<Vec<u8>, Error> {
+    let input = input.strip_prefix("0x").unwrap_or(input);
+    if input.is_empty() || input.len() % 2 != 0 {
+        return Err(Error::InvalidSignature);
+    }
+    (0..input.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&input[i..i + 2], 16).map_err(|_| Error::InvalidSignature))
+        .collect()
+}
+
+/// Mirrors polkadot-js's `SignerPayloadJSON`, the shape `signer.signPayload`
+/// expects for a submittable extrinsic rather than a raw blob.
+#[derive(Debug, Clone)]
+pub struct SignerPayload {
+    pub address: String,
+    pub genesis_hash: String,
+    pub block_hash: String,
+    pub block_number: String,
+    pub era: String,
+    pub nonce: String,
+    /// Hex SCALE-encoded call.
+    pub method: String,
+    pub spec_version: String,
+    pub transaction_version: String,
+    pub tip: String,
+    pub signed_extensions: Vec<String>,
+    pub version: u8,
+    /// Set alongside `metadata_hash` when the chain supports metadata-hash checks.
+    pub mode: Option<u8>,
+    pub metadata_hash: Option<String>,
+}
+
+impl Default for SignerPayload {
+    fn default() -> Self {
+        Self {
+            address: String::new(),
+            genesis_hash: String::new(),
+            block_hash: String::new(),
+            block_number: "0x00000000".into(),
+            era: "0x00".into(),
+            nonce: "0x00000000".into(),
+            method: String::new(),
+            spec_version: "0x00000000".into(),
+            transaction_version: "0x00000000".into(),
+            tip: "0x00000000".into(),
+            signed_extensions: vec![],
+            version: 4,
+            mode: None,
+            metadata_hash: None,
+        }
+    }
+}
+
+impl SignerPayload {
+    fn to_object(&self) -> Object {
+        let o = Object::new();
+        Reflect::set(&o, &"address".into(), &self.address.as_str().into()).unwrap();
+        Reflect::set(&o, &"genesisHash".into(), &self.genesis_hash.as_str().into()).unwrap();
+        Reflect::set(&o, &"blockHash".into(), &self.block_hash.as_str().into()).unwrap();
+        Reflect::set(&o, &"blockNumber".into(), &self.block_number.as_str().into()).unwrap();
+        Reflect::set(&o, &"era".into(), &self.era.as_str().into()).unwrap();
+        Reflect::set(&o, &"nonce".into(), &self.nonce.as_str().into()).unwrap();
+        Reflect::set(&o, &"method".into(), &self.method.as_str().into()).unwrap();
+        Reflect::set(&o, &"specVersion".into(), &self.spec_version.as_str().into()).unwrap();
+        Reflect::set(
+            &o,
+            &"transactionVersion".into(),
+            &self.transaction_version.as_str().into(),
+        )
+        .unwrap();
+        Reflect::set(&o, &"tip".into(), &self.tip.as_str().into()).unwrap();
+        let exts = Array::new();
+        for ext in &self.signed_extensions {
+            exts.push(&JsValue::from_str(ext));
+        }
+        Reflect::set(&o, &"signedExtensions".into(), &exts).unwrap();
+        Reflect::set(&o, &"version".into(), &self.version.into()).unwrap();
+        if let Some(mode) = self.mode {
+            Reflect::set(&o, &"mode".into(), &mode.into()).unwrap();
+        }
+        if let Some(hash) = &self.metadata_hash {
+            Reflect::set(&o, &"metadataHash".into(), &hash.as_str().into()).unwrap();
+        }
+        o
+    }
 }
 
 #[wasm_bindgen]
@@ -174,13 +533,13 @@ pub enum Error {
 pub struct Account {
     name: String,
     address: String,
-    net: Network,
+    net: NetworkInfo,
 }
 
 #[wasm_bindgen]
 impl Account {
     #[wasm_bindgen(constructor)]
-    pub fn new(name: &str, address: &str, net: Network) -> Self {
+    pub fn new(name: &str, address: &str, net: NetworkInfo) -> Self {
         Account {
             name: name.to_string(),
             address: address.to_string(),
@@ -196,32 +555,232 @@ impl Account {
         self.address.clone()
     }
     #[wasm_bindgen(getter)]
-    pub fn network(&self) -> Network {
-        self.net
+    pub fn network(&self) -> NetworkInfo {
+        self.net.clone()
     }
+
+    /// Re-encodes this account's public key into `network`'s SS58 format,
+    /// e.g. to display a Polkadot account's address on Kusama.
+    #[wasm_bindgen(js_name = addressFor)]
+    pub fn address_for(&self, network: &NetworkInfo) -> Result<String, Error> {
+        let decoded = bs58::decode(self.address.as_str())
+            .into_vec()
+            .map_err(|_| Error::InvalidAddress)?;
+        // payload = prefix (1 or 2 bytes) ++ 32-byte public key, followed by a 2-byte checksum.
+        let prefix_len = decoded.len().checked_sub(34).ok_or(Error::InvalidAddress)?;
+        let pubkey = decoded
+            .get(prefix_len..prefix_len + 32)
+            .ok_or(Error::InvalidAddress)?;
+
+        let mut payload = ss58_prefix_bytes(network.ss58_prefix);
+        payload.extend_from_slice(pubkey);
+        payload.extend_from_slice(&ss58_checksum(&payload));
+
+        Ok(bs58::encode(payload).into_string())
+    }
+}
+
+/// Everything needed to display and address an account on a given chain.
+/// Ships with well-known chains but callers may [`register_network`]
+/// additional ones at runtime.
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+pub struct NetworkInfo {
+    genesis_hash: String,
+    ss58_prefix: u16,
+    decimals: u8,
+    token_symbol: String,
+    display_name: String,
 }
 
 #[wasm_bindgen]
-#[derive(Debug, Clone, Copy)]
-pub enum Network {
-    Generic,
-    Kusama,
-    Polkadot,
-    Kreivo,
+impl NetworkInfo {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        genesis_hash: String,
+        ss58_prefix: u16,
+        decimals: u8,
+        token_symbol: String,
+        display_name: String,
+    ) -> Self {
+        Self {
+            genesis_hash,
+            ss58_prefix,
+            decimals,
+            token_symbol,
+            display_name,
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = genesisHash)]
+    pub fn genesis_hash(&self) -> String {
+        self.genesis_hash.clone()
+    }
+    #[wasm_bindgen(getter, js_name = ss58Prefix)]
+    pub fn ss58_prefix(&self) -> u16 {
+        self.ss58_prefix
+    }
+    #[wasm_bindgen(getter)]
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+    #[wasm_bindgen(getter, js_name = tokenSymbol)]
+    pub fn token_symbol(&self) -> String {
+        self.token_symbol.clone()
+    }
+    #[wasm_bindgen(getter, js_name = displayName)]
+    pub fn display_name(&self) -> String {
+        self.display_name.clone()
+    }
+}
+
+impl NetworkInfo {
+    fn generic(genesis_hash: impl Into<String>) -> Self {
+        Self {
+            genesis_hash: genesis_hash.into(),
+            ss58_prefix: 42,
+            decimals: 0,
+            token_symbol: String::new(),
+            display_name: "Generic".to_string(),
+        }
+    }
 }
 
 const KSM: &str = "0xb0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe";
 const DOT: &str = "0x91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3";
 const KREIVO: &str = "0xc710a5f16adc17bcd212cff0aedcbf1c1212a043cdc0fb2dcba861efe5305b01";
 
-impl From<JsValue> for Network {
+thread_local! {
+    static NETWORKS: RefCell<HashMap<String, NetworkInfo>> = RefCell::new(
+        [
+            NetworkInfo::new(DOT.to_string(), 0, 10, "DOT".to_string(), "Polkadot".to_string()),
+            NetworkInfo::new(KSM.to_string(), 2, 12, "KSM".to_string(), "Kusama".to_string()),
+            // Kreivo has no entry of its own in the ss58-registry (its para ID,
+            // 2281, is a distinct registry and not a substitute for one) and so
+            // resolves addresses with the relay chain's SS58 format, same as any
+            // other Kusama parachain without a registered prefix.
+            NetworkInfo::new(KREIVO.to_string(), 2, 12, "KSM".to_string(), "Kreivo".to_string()),
+        ]
+        .into_iter()
+        .map(|info| (info.genesis_hash.clone(), info))
+        .collect()
+    );
+}
+
+/// Registers a chain (or overrides a built-in one) so accounts on it resolve
+/// to the right [`NetworkInfo`] instead of falling back to the generic entry.
+#[wasm_bindgen(js_name = registerNetwork)]
+pub fn register_network(info: NetworkInfo) {
+    NETWORKS.with(|networks| {
+        networks.borrow_mut().insert(info.genesis_hash.clone(), info);
+    });
+}
+
+fn lookup_network(genesis_hash: &str) -> NetworkInfo {
+    NETWORKS
+        .with(|networks| networks.borrow().get(genesis_hash).cloned())
+        .unwrap_or_else(|| NetworkInfo::generic(genesis_hash))
+}
+
+impl From<JsValue> for NetworkInfo {
     fn from(value: JsValue) -> Self {
-        let value = value.as_string();
-        match value.as_deref() {
-            Some(KSM) => Network::Kusama,
-            Some(DOT) => Network::Polkadot,
-            Some(KREIVO) => Network::Kreivo,
-            _ => Network::Generic,
-        }
+        lookup_network(value.as_string().unwrap_or_default().as_str())
+    }
+}
+
+/// Encodes an SS58 network identifier into its 1- or 2-byte prefix form.
+fn ss58_prefix_bytes(prefix: u16) -> Vec<u8> {
+    if prefix < 64 {
+        vec![prefix as u8]
+    } else {
+        let first = (((prefix & 0b0000_0000_1111_1100) >> 2) as u8) | 0b0100_0000;
+        let second = ((prefix >> 8) as u8) | (((prefix & 0b0000_0000_0000_0011) << 6) as u8);
+        vec![first, second]
+    }
+}
+
+fn ss58_checksum(payload: &[u8]) -> [u8; 2] {
+    let mut hasher = Blake2b512::new();
+    hasher.update(b"SS58PRE");
+    hasher.update(payload);
+    let hash = hasher.finalize();
+    [hash[0], hash[1]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_selected_picks_nearest_valid_index() {
+        assert_eq!(clamp_selected(None, 0), None);
+        assert_eq!(clamp_selected(Some(3), 0), None);
+        assert_eq!(clamp_selected(None, 3), Some(0));
+        assert_eq!(clamp_selected(Some(1), 3), Some(1));
+        assert_eq!(clamp_selected(Some(9), 3), Some(2));
+    }
+
+    #[test]
+    fn multi_signature_hex_round_trips() {
+        let sr25519 = MultiSignature {
+            scheme: CryptoScheme::Sr25519,
+            bytes: vec![0x11; 64],
+        };
+        let hex = sr25519.to_hex();
+        let decoded = MultiSignature::from_hex(hex.as_str()).unwrap();
+        assert_eq!(decoded.scheme, CryptoScheme::Sr25519);
+        assert_eq!(decoded.bytes, sr25519.bytes);
+
+        let ecdsa = MultiSignature {
+            scheme: CryptoScheme::Ecdsa,
+            bytes: vec![0x22; 65],
+        };
+        let decoded = MultiSignature::from_hex(ecdsa.to_hex().as_str()).unwrap();
+        assert_eq!(decoded.scheme, CryptoScheme::Ecdsa);
+        assert_eq!(decoded.bytes, ecdsa.bytes);
+    }
+
+    #[test]
+    fn multi_signature_from_hex_rejects_unprefixed_signature() {
+        // 64 raw bytes, no leading scheme byte: the first byte is misread as
+        // the scheme and `0x11` isn't a valid one.
+        let raw = "0x".to_string() + &"11".repeat(64);
+        assert!(matches!(
+            MultiSignature::from_hex(raw.as_str()),
+            Err(Error::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn multi_signature_from_raw_hex_infers_scheme_from_length() {
+        let raw64 = "0x".to_string() + &"33".repeat(64);
+        let decoded = MultiSignature::from_raw_hex(raw64.as_str()).unwrap();
+        assert_eq!(decoded.scheme, CryptoScheme::Sr25519);
+        assert_eq!(decoded.bytes.len(), 64);
+
+        let raw65 = "0x".to_string() + &"44".repeat(65);
+        let decoded = MultiSignature::from_raw_hex(raw65.as_str()).unwrap();
+        assert_eq!(decoded.scheme, CryptoScheme::Ecdsa);
+        assert_eq!(decoded.bytes.len(), 65);
+
+        let wrong_len = "0x".to_string() + &"55".repeat(63);
+        assert!(matches!(
+            MultiSignature::from_raw_hex(wrong_len.as_str()),
+            Err(Error::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn address_for_reencodes_known_polkadot_address_to_kusama() {
+        // Well-known "Alice" dev account, re-encoded from Polkadot's SS58
+        // format to Kusama's.
+        let alice_dot = Account::new(
+            "Alice",
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+            NetworkInfo::new(DOT.to_string(), 0, 10, "DOT".to_string(), "Polkadot".to_string()),
+        );
+        let kusama = NetworkInfo::new(KSM.to_string(), 2, 12, "KSM".to_string(), "Kusama".to_string());
+        let address = alice_dot.address_for(&kusama).unwrap();
+        assert_eq!(address, "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F");
     }
 }